@@ -13,6 +13,48 @@ macro_rules! derive_error {
     };
 }
 
+/// Finds the `#[wasm_bindgen(...)]` (or bare `#[wasm_bindgen]`) attribute on a derive input, if any.
+fn find_wasm_bindgen_meta(attrs: &[syn::Attribute]) -> Option<syn::Meta> {
+    attrs.iter().find_map(|attr| {
+        attr.parse_meta()
+            .ok()
+            .and_then(|meta| match meta.path().is_ident("wasm_bindgen") {
+                true => Some(meta),
+                false => None,
+            })
+    })
+}
+
+/// Extracts the `js_name = "..."` set on a `#[wasm_bindgen(...)]` attribute, if any.
+fn js_class_name(wasm_bindgen_meta: syn::Meta) -> Option<String> {
+    match wasm_bindgen_meta {
+        syn::Meta::List(list) => Some(list),
+        _ => None,
+    }
+    .and_then(|meta_list| {
+        meta_list.nested.iter().find_map(|nested_meta| {
+            let maybe_meta = match nested_meta {
+                syn::NestedMeta::Meta(meta) => Some(meta),
+                _ => None,
+            };
+
+            maybe_meta
+                .and_then(|meta| match meta {
+                    syn::Meta::NameValue(name_value) => Some(name_value),
+                    _ => None,
+                })
+                .and_then(|name_value| match name_value.path.is_ident("js_name") {
+                    true => Some(name_value.lit.clone()),
+                    false => None,
+                })
+                .and_then(|lit| match lit {
+                    syn::Lit::Str(str) => Some(str.value()),
+                    _ => None,
+                })
+        })
+    })
+}
+
 /// Implementation of [`TryFromJsValue`] mirrored from here [`wasm-bindgen-derive`](https://github.com/fjarri/wasm-bindgen-derive/blob/master/src/lib.rs)
 /// It serves as a basis for workarounds for some lapses of functionality in [`wasm-bindgen`](https://crates.io/crates/wasm-bindgen).
 ///
@@ -25,6 +67,11 @@ macro_rules! derive_error {
 ///  - this derivation must be be positioned before `#[wasm_bindgen]`;
 ///  - the type must implement [`Clone`].
 ///
+/// It can also be derived on a C-like enum (variants without fields), since `#[wasm_bindgen]`
+/// exports those as numeric values rather than objects with a `ptr`. In that case the generated
+/// `TryFrom<&JsValue>` reads the value as an `f64`, range-checks it against the enum's
+/// discriminants, and maps it to the matching variant, erroring on an out-of-range value.
+///
 /// The macro is authored by [**@AlexKorn**](https://github.com/AlexKorn)
 /// based on the idea of [**@aweinstock314**](https://github.com/aweinstock314).
 /// See [this](https://github.com/rustwasm/wasm-bindgen/issues/2231#issuecomment-656293288)
@@ -136,52 +183,63 @@ pub fn derive_try_from_jsvalue(input: TokenStream) -> TokenStream {
     let name = input.ident;
     let data = input.data;
 
-    match data {
+    match &data {
         Data::Struct(_) => {}
-        _ => return derive_error!("TryFromJsValue may only be derived on structs"),
+        Data::Enum(data_enum) => {
+            let has_fielded_variant = data_enum
+                .variants
+                .iter()
+                .any(|variant| !matches!(variant.fields, syn::Fields::Unit));
+            if has_fielded_variant {
+                return derive_error!(
+                    "TryFromJsValue may only be derived on C-like enums (variants without fields)"
+                );
+            }
+        }
+        _ => return derive_error!("TryFromJsValue may only be derived on structs or C-like enums"),
     };
 
-    let wasm_bindgen_meta = input.attrs.iter().find_map(|attr| {
-        attr.parse_meta()
-            .ok()
-            .and_then(|meta| match meta.path().is_ident("wasm_bindgen") {
-                true => Some(meta),
-                false => None,
-            })
-    });
+    let wasm_bindgen_meta = find_wasm_bindgen_meta(&input.attrs);
     if wasm_bindgen_meta.is_none() {
         return derive_error!(
             "TryFromJsValue can be defined only on struct exported to wasm with #[wasm_bindgen]"
         );
     }
 
-    let maybe_js_class = wasm_bindgen_meta
-        .and_then(|meta| match meta {
-            syn::Meta::List(list) => Some(list),
-            _ => None,
-        })
-        .and_then(|meta_list| {
-            meta_list.nested.iter().find_map(|nested_meta| {
-                let maybe_meta = match nested_meta {
-                    syn::NestedMeta::Meta(meta) => Some(meta),
-                    _ => None,
-                };
+    // C-like enums cross the `wasm-bindgen` boundary as plain numbers rather than objects with a
+    // `ptr`, so there's no classname/pointer dance to generate: just range-check the discriminant.
+    if let Data::Enum(data_enum) = &data {
+        let variant_idents: Vec<_> = data_enum.variants.iter().map(|v| v.ident.clone()).collect();
 
-                maybe_meta
-                    .and_then(|meta| match meta {
-                        syn::Meta::NameValue(name_value) => Some(name_value),
-                        _ => None,
-                    })
-                    .and_then(|name_value| match name_value.path.is_ident("js_name") {
-                        true => Some(name_value.lit.clone()),
-                        false => None,
-                    })
-                    .and_then(|lit| match lit {
-                        syn::Lit::Str(str) => Some(str.value()),
-                        _ => None,
-                    })
-            })
-        });
+        let expanded = quote! {
+            impl #name {
+                pub fn __get_classname() -> &'static str {
+                    ::core::stringify!(#name)
+                }
+            }
+
+            impl ::core::convert::TryFrom<&::wasm_bindgen::JsValue> for #name {
+                type Error = ::wasm_bindgen::JsValue;
+
+                fn try_from(js: &::wasm_bindgen::JsValue) -> Result<Self, Self::Error> {
+                    let classname = Self::__get_classname();
+
+                    let value = js.as_f64().ok_or_else(|| {
+                        ::wasm_bindgen::JsValue::from_str(format!("Value supplied as {} is not a number", classname).as_str())
+                    })?;
+
+                    match value {
+                        #(v if v == #name::#variant_idents as u32 as f64 => Ok(#name::#variant_idents),)*
+                        _ => Err(::wasm_bindgen::JsValue::from_str(format!("{} is out of range for {}", value, classname).as_str())),
+                    }
+                }
+            }
+        };
+
+        return TokenStream::from(expanded);
+    }
+
+    let maybe_js_class = wasm_bindgen_meta.and_then(js_class_name);
 
     let wasm_bindgen_macro_invocaton = match maybe_js_class {
         Some(class) => format!("wasm_bindgen(js_class = \"{}\")", class),
@@ -245,17 +303,32 @@ pub fn derive_try_from_jsvalue(input: TokenStream) -> TokenStream {
                     .and_then(|v| v.as_string())
                     .ok_or_else(|| ::wasm_bindgen::JsValue::from_str("Failed to get classname"))?;
 
-                if object_classname.as_str() == classname {
-                    let ptr = ::js_sys::Reflect::get(js, &::wasm_bindgen::JsValue::from_str("ptr"))
-                        .map_err(|err| ::wasm_bindgen::JsValue::from_str(format!("{:?}", err).as_str()))?;
-                    let ptr_u32: u32 = ptr.as_f64().ok_or(::wasm_bindgen::JsValue::NULL)
-                        .map_err(|err| ::wasm_bindgen::JsValue::from_str(format!("{:?}", err).as_str()))?
-                        as u32;
-                    let instance_ref = unsafe { #name::ref_from_abi(ptr_u32) };
-                    Ok(instance_ref.clone())
-                } else {
-                    Err(::wasm_bindgen::JsValue::from_str(format!("Cannot convert {} to {}", object_classname, classname).as_str()))
+                if object_classname.as_str() != classname {
+                    return Err(::wasm_bindgen::JsValue::from_str(format!("Cannot convert {} to {}", object_classname, classname).as_str()));
                 }
+
+                // `wasm-bindgen` stores each instance's heap pointer directly on the JS wrapper
+                // object, in a plain data property: `__wbg_ptr` since 0.2.85, `ptr` before that.
+                // Reading it with `Reflect::get` is non-consuming, so (unlike calling a generated
+                // Rust method that takes `self` by value) this never destroys the wrapper just to
+                // borrow-convert it. The property only ever holds the raw pointer as a JS number,
+                // never the instance's real `Abi` type, so we rebuild that type (currently
+                // `WasmPtr<WasmRefCell<Self>>`, not plain `u32`) ourselves before calling
+                // `ref_from_abi`, rather than assuming the two are interchangeable.
+                let ptr = ::js_sys::Reflect::get(js, &::wasm_bindgen::JsValue::from_str("__wbg_ptr"))
+                    .and_then(|ptr| match ptr.is_undefined() {
+                        true => ::js_sys::Reflect::get(js, &::wasm_bindgen::JsValue::from_str("ptr")),
+                        false => Ok(ptr),
+                    })
+                    .map_err(|err| ::wasm_bindgen::JsValue::from_str(format!("{:?}", err).as_str()))?;
+                let ptr_u32: u32 = ptr.as_f64()
+                    .ok_or_else(|| ::wasm_bindgen::JsValue::from_str(format!("{} has no instance pointer", classname).as_str()))?
+                    as u32;
+
+                let ptr_abi: ::wasm_bindgen::__rt::WasmPtr<::wasm_bindgen::__rt::WasmRefCell<#name>> =
+                    ::wasm_bindgen::__rt::WasmPtr::from_usize(ptr_u32 as usize);
+
+                Ok(unsafe { #name::ref_from_abi(ptr_abi) }.clone())
             }
         }
     };
@@ -263,10 +336,33 @@ pub fn derive_try_from_jsvalue(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+// `TryVecToJsArray`, `TryJsArrayToVec` and `WasmOption` are commonly derived together on the same
+// struct, all reading the same single `#[ergo(...)]` attribute. A separate darling `FromDeriveInput`
+// struct per derive, each only declaring the keys it reads, made every other derive's keys
+// "unknown" to the ones that didn't declare them, so parsing panicked (`UnknownField`) the moment
+// a struct combined e.g. `#[ergo(array_type = "...", generate_extern)]` with `TryJsArrayToVec`.
+// One shared opts struct declaring every key any of the three derives understands sidesteps that;
+// each derive below still only reads the fields that are actually its own.
 #[derive(FromDeriveInput)]
 #[darling(attributes(ergo))]
-struct TryVecToJsArrayOpts {
-    array_type: syn::Ident,
+struct ErgoOpts {
+    /// Used by [`TryVecToJsArray`] and [`TryJsArrayToVec`].
+    #[darling(default)]
+    array_type: Option<syn::Ident>,
+    /// Used by [`WasmOption`].
+    #[darling(default)]
+    option_type: Option<syn::Ident>,
+    /// Used by [`TryVecToJsArray`]. When set, also emits the `#[wasm_bindgen] extern "C" { ... }`
+    /// block declaring `array_type` as `typescript_type = "<js_name>[]"`, so callers no longer
+    /// have to hand-write it next to the derive.
+    #[darling(default)]
+    generate_extern: bool,
+    /// Used by [`TryJsArrayToVec`]. `"collect"` additionally generates `try_as_vec_collect`,
+    /// which aggregates every invalid element instead of failing on the first one. Any other
+    /// value (the default) leaves the derive's behavior unchanged: only the fail-fast
+    /// `try_as_vec` is generated.
+    #[darling(default)]
+    on_invalid: Option<String>,
 }
 
 /// Derive `TryVecToJsArray` that provides methods to convert a Rust `Vec` of wasm binded structures to `JsValue`.
@@ -279,6 +375,12 @@ struct TryVecToJsArrayOpts {
 ///  * `#[wasm_bindgen`] is specified AFTER the previously mentioned points
 ///  * The struct derives [`Clone`]
 ///
+/// Add `#[ergo(array_type = "StructArrayType", generate_extern)]` to also have the derive emit
+/// the `#[wasm_bindgen] extern "C" { ... }` block declaring `StructArrayType` itself, instead of
+/// hand-writing it next to the derive. The TypeScript type string is `"<js_name>[]"`, where
+/// `js_name` is taken from the struct's own `#[wasm_bindgen(js_name = "...")]` (falling back to
+/// the struct's Rust name).
+///
 /// ```
 /// use js_sys::Error;
 /// use wasm_bindgen::JsCast;
@@ -309,33 +411,50 @@ struct TryVecToJsArrayOpts {
 pub fn derive_try_vec_to_js_array(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
     let input_ref = &input;
-    let attrs = TryVecToJsArrayOpts::from_derive_input(input_ref).unwrap();
+    let attrs = ErgoOpts::from_derive_input(input_ref).unwrap();
     let name = input.ident;
     let data = input.data;
 
     match data {
-        Data::Struct(_) => {}
-        _ => return derive_error!("TryVecToJsArray may only be derived on structs"),
+        Data::Struct(_) | Data::Enum(_) => {}
+        _ => return derive_error!("TryVecToJsArray may only be derived on structs or C-like enums"),
     };
 
-    let wasm_bindgen_meta = input.attrs.iter().find_map(|attr| {
-        attr.parse_meta()
-            .ok()
-            .and_then(|meta| match meta.path().is_ident("wasm_bindgen") {
-                true => Some(meta),
-                false => None,
-            })
-    });
+    let wasm_bindgen_meta = find_wasm_bindgen_meta(&input.attrs);
     if wasm_bindgen_meta.is_none() {
         return derive_error!(
             "TryVecToJsArray can be defined only on struct exported to wasm with #[wasm_bindgen]"
         );
     }
 
+    let Some(array_type) = attrs.array_type else {
+        return derive_error!("TryVecToJsArray requires #[ergo(array_type = \"...\")]");
+    };
+
     let trait_name = format_ident!("__ergo__{}__TryToJsArray", name);
-    let return_type = format_ident!("{}", attrs.array_type);
+    let return_type = format_ident!("{}", array_type);
+
+    let extern_block = if attrs.generate_extern {
+        let js_name = wasm_bindgen_meta
+            .clone()
+            .and_then(js_class_name)
+            .unwrap_or_else(|| name.to_string());
+        let typescript_type = format!("{}[]", js_name);
+
+        quote! {
+            #[wasm_bindgen]
+            extern "C" {
+                #[wasm_bindgen(typescript_type = #typescript_type)]
+                pub type #return_type;
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     let expanded = quote! {
+        #extern_block
+
         #[allow(non_camel_case_types)]
         pub trait #trait_name {
             type ReturnType;
@@ -378,6 +497,12 @@ pub fn derive_try_vec_to_js_array(input: TokenStream) -> TokenStream {
 ///  * `#[wasm_bindgen`] is specified AFTER the previously mentioned points
 ///  * The struct derives [`Clone`]
 ///
+/// By default, `try_as_vec` fails fast: the first element that doesn't convert aborts the whole
+/// call with that element's error. Add `#[ergo(array_type = "StructArrayType", on_invalid = "collect")]`
+/// to also generate `try_as_vec_collect`, which converts every element instead, collecting the
+/// index and reason of every failure into one structured `JsValue` error — useful for validating
+/// large arrays coming from untrusted JS callers without that opaque single-error behavior.
+///
 /// ```
 /// use js_sys::Error;
 /// use wasm_bindgen::JsCast;
@@ -411,31 +536,76 @@ pub fn derive_try_vec_to_js_array(input: TokenStream) -> TokenStream {
 pub fn derive_try_js_array_to_vec(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
     let input_ref = &input;
-    let attrs = TryVecToJsArrayOpts::from_derive_input(input_ref).unwrap();
+    let attrs = ErgoOpts::from_derive_input(input_ref).unwrap();
     let name = input.ident;
     let data = input.data;
 
     match data {
-        Data::Struct(_) => {}
-        _ => return derive_error!("TryJsArrayToVec may only be derived on structs"),
+        Data::Struct(_) | Data::Enum(_) => {}
+        _ => return derive_error!("TryJsArrayToVec may only be derived on structs or C-like enums"),
     };
 
-    let wasm_bindgen_meta = input.attrs.iter().find_map(|attr| {
-        attr.parse_meta()
-            .ok()
-            .and_then(|meta| match meta.path().is_ident("wasm_bindgen") {
-                true => Some(meta),
-                false => None,
-            })
-    });
+    let wasm_bindgen_meta = find_wasm_bindgen_meta(&input.attrs);
     if wasm_bindgen_meta.is_none() {
         return derive_error!(
             "TryJsArrayToVec can be defined only on struct exported to wasm with #[wasm_bindgen]"
         );
     }
 
+    let Some(array_type) = attrs.array_type else {
+        return derive_error!("TryJsArrayToVec requires #[ergo(array_type = \"...\")]");
+    };
+
     let trait_name = format_ident!("__ergo__{}__TryJsArrayToVec", name);
-    let array_type = format_ident!("{}", attrs.array_type);
+    let array_type = format_ident!("{}", array_type);
+
+    let collect_impl = if attrs.on_invalid.as_deref() == Some("collect") {
+        let collect_trait_name = format_ident!("__ergo__{}__TryJsArrayToVecCollect", name);
+
+        quote! {
+            #[allow(non_camel_case_types)]
+            pub trait #collect_trait_name {
+                type ReturnType;
+
+                /// Like `try_as_vec`, but never fails fast: every element is converted, and the
+                /// index and reason of every failure is collected into one structured `JsValue`
+                /// error (an array of `{index, message}` objects) instead of stopping at the
+                /// first bad element.
+                fn try_as_vec_collect(&self) -> Result<Vec<Self::ReturnType>, ::wasm_bindgen::JsValue>;
+            }
+
+            impl #collect_trait_name for &#array_type {
+                type ReturnType = #name;
+
+                fn try_as_vec_collect(&self) -> Result<Vec<Self::ReturnType>, ::wasm_bindgen::JsValue> {
+                    let js_array: &::js_sys::Array = self.dyn_ref().map_or_else(|| Err(JsValue::from_str("try_as_vec_collect: argument wasn't an array type")), |v| Ok(v))?;
+                    let mut rust_vec = Vec::<Self::ReturnType>::with_capacity(js_array.length() as usize);
+                    let mut errors = Vec::<::wasm_bindgen::JsValue>::new();
+
+                    for (index, js) in js_array.iter().enumerate() {
+                        match <Self::ReturnType as ::std::convert::TryFrom<&::wasm_bindgen::JsValue>>::try_from(&js) {
+                            Ok(elem) => rust_vec.push(elem),
+                            Err(err) => {
+                                let message = err.as_string().unwrap_or_else(|| format!("{:?}", err));
+                                let entry = ::js_sys::Object::new();
+                                let _ = ::js_sys::Reflect::set(&entry, &::wasm_bindgen::JsValue::from_str("index"), &::wasm_bindgen::JsValue::from_f64(index as f64));
+                                let _ = ::js_sys::Reflect::set(&entry, &::wasm_bindgen::JsValue::from_str("message"), &::wasm_bindgen::JsValue::from_str(&message));
+                                errors.push(entry.into());
+                            }
+                        }
+                    }
+
+                    if errors.is_empty() {
+                        Ok(rust_vec)
+                    } else {
+                        Err(errors.into_iter().collect::<::js_sys::Array>().into())
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     let expanded = quote! {
         #[allow(non_camel_case_types)]
@@ -459,6 +629,212 @@ pub fn derive_try_js_array_to_vec(input: TokenStream) -> TokenStream {
                 Ok(rust_vec)
             }
         }
+
+        #collect_impl
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive native `Vec<T>` ABI support for a type exported with `#[wasm_bindgen]`.
+///
+/// Since `wasm-bindgen` 0.2.105, `#[wasm_bindgen]` generates `WasmDescribeVector` and
+/// `VectorIntoWasmAbi`/`VectorFromWasmAbi` for every exported struct and C-like enum on its own,
+/// which is already everything needed for `Vec<MyType>` to work as a `#[wasm_bindgen]` argument
+/// or return type. Deriving `WasmVecAbi` on top of that generates a second, conflicting set of
+/// the same impls (`E0119`), so this derive is gated behind the `legacy-vec-abi` feature and is
+/// only meant to be enabled by crates still pinned to `wasm-bindgen` < 0.2.105, where those impls
+/// don't exist yet. Crates on a current `wasm-bindgen` should just drop this derive entirely.
+///
+/// `WasmVecAbi` depends on the following:
+///  * The struct derives [`TryFromJsValue`] (used to rebuild elements coming back from JS)
+///  * `#[wasm_bindgen]` is specified AFTER the previously mentioned derive
+///  * The struct derives [`Clone`]
+///
+/// Note that an element that fails to convert on the way back from JS aborts the call (via
+/// `wasm_bindgen`'s own `expect_throw` inside `js_value_vector_from_abi`) rather than returning a
+/// `Result`, since `VectorFromWasmAbi` has no room for fallibility — this matches how
+/// `wasm-bindgen` itself behaves for e.g. `Vec<String>`.
+///
+/// This example assumes the `legacy-vec-abi` feature is enabled; without it the derive emits a
+/// `compile_error!` pointing here instead.
+///
+/// ```
+/// use wasm_bindgen::prelude::wasm_bindgen;
+/// use ergo_wasm_derive::{TryFromJsValue, WasmVecAbi};
+///
+/// #[derive(TryFromJsValue, WasmVecAbi)]
+/// #[wasm_bindgen]
+/// #[derive(Clone)]
+/// pub struct MyType(pub usize);
+///
+/// // `Vec<MyType>` can now be used directly in argument and return position.
+/// #[wasm_bindgen]
+/// pub fn foo(values: Vec<MyType>) -> Vec<MyType> {
+///     values
+/// }
+/// ```
+#[proc_macro_derive(WasmVecAbi)]
+pub fn derive_wasm_vec_abi(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let data = input.data;
+
+    match data {
+        Data::Struct(_) => {}
+        _ => return derive_error!("WasmVecAbi may only be derived on structs"),
+    };
+
+    let wasm_bindgen_meta = find_wasm_bindgen_meta(&input.attrs);
+    if wasm_bindgen_meta.is_none() {
+        return derive_error!(
+            "WasmVecAbi can be defined only on struct exported to wasm with #[wasm_bindgen]"
+        );
+    }
+
+    let exported_name = wasm_bindgen_meta
+        .and_then(js_class_name)
+        .unwrap_or_else(|| name.to_string());
+    let name_len = exported_name.len() as u32;
+    let name_chars: Vec<u32> = exported_name.chars().map(|c| c as u32).collect();
+
+    // `#[wasm_bindgen]` has generated these same impls for every exported struct since
+    // wasm-bindgen 0.2.105, so deriving them again conflicts (E0119) on anything current. Only
+    // crates that have opted in via `legacy-vec-abi`, acknowledging they're pinned below that
+    // version, get the impls below; everyone else gets a clear error instead of a landmine that
+    // only surfaces on the next `cargo update`.
+    let expanded = quote! {
+        #[cfg(not(feature = "legacy-vec-abi"))]
+        ::core::compile_error!(concat!(
+            "WasmVecAbi is redundant on wasm-bindgen >= 0.2.105: #[wasm_bindgen] already generates ",
+            "WasmDescribeVector/VectorIntoWasmAbi/VectorFromWasmAbi for every exported struct, and ",
+            "deriving WasmVecAbi on top of that conflicts with them (E0119). Drop this derive, or ",
+            "enable a `legacy-vec-abi` feature on the crate deriving it if you are pinned below ",
+            "wasm-bindgen 0.2.105.",
+        ));
+
+        #[cfg(feature = "legacy-vec-abi")]
+        impl ::wasm_bindgen::convert::TryFromJsValue for #name {
+            fn try_from_js_value_ref(value: &::wasm_bindgen::JsValue) -> ::core::option::Option<Self> {
+                <Self as ::core::convert::TryFrom<&::wasm_bindgen::JsValue>>::try_from(value).ok()
+            }
+        }
+
+        #[cfg(feature = "legacy-vec-abi")]
+        impl ::wasm_bindgen::describe::WasmDescribeVector for #name {
+            fn describe_vector() {
+                use ::wasm_bindgen::describe::inform;
+
+                inform(::wasm_bindgen::describe::VECTOR);
+                inform(::wasm_bindgen::describe::NAMED_EXTERNREF);
+                inform(#name_len);
+                #(inform(#name_chars);)*
+            }
+        }
+
+        #[cfg(feature = "legacy-vec-abi")]
+        impl ::wasm_bindgen::convert::VectorIntoWasmAbi for #name {
+            type Abi = <::std::boxed::Box<[::wasm_bindgen::JsValue]> as ::wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+            fn vector_into_abi(vector: ::std::boxed::Box<[Self]>) -> Self::Abi {
+                ::wasm_bindgen::convert::js_value_vector_into_abi(vector)
+            }
+        }
+
+        #[cfg(feature = "legacy-vec-abi")]
+        impl ::wasm_bindgen::convert::VectorFromWasmAbi for #name {
+            type Abi = <::std::boxed::Box<[::wasm_bindgen::JsValue]> as ::wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+            unsafe fn vector_from_abi(js: Self::Abi) -> ::std::boxed::Box<[Self]> {
+                ::wasm_bindgen::convert::js_value_vector_from_abi(js)
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive `WasmOption` that codifies the `Option<&T>` recipe documented on [`TryFromJsValue`]:
+/// a `typescript_type = "MyType | null"` extern type, plus a `try_as_option` method that turns
+/// its `&JsValue` into `Option<MyType>` without consuming the value the way a plain `Option<T>`
+/// argument would.
+///
+/// `WasmOption` depends on the following:
+///  * The struct derives [`TryFromJsValue`]
+///  * The struct defines the attribute `#[ergo(option_type = "OptionMyType")]`
+///  * `#[wasm_bindgen]` is specified AFTER the previously mentioned points
+///  * The struct derives [`Clone`]
+///
+/// ```
+/// use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+/// use ergo_wasm_derive::{TryFromJsValue, WasmOption};
+///
+/// #[derive(TryFromJsValue, WasmOption)]
+/// #[ergo(option_type = "OptionMyType")]
+/// #[wasm_bindgen]
+/// #[derive(Clone)]
+/// pub struct MyType(pub usize);
+///
+/// // Use this type in the function signature.
+/// pub fn foo(value: &OptionMyType) -> Result<usize, JsValue> {
+///     Ok(value.try_as_option()?.map(|value| value.0).unwrap_or_default())
+/// }
+/// ```
+#[proc_macro_derive(WasmOption, attributes(ergo))]
+pub fn derive_wasm_option(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let input_ref = &input;
+    let attrs = ErgoOpts::from_derive_input(input_ref).unwrap();
+    let name = input.ident;
+    let data = input.data;
+
+    match data {
+        Data::Struct(_) | Data::Enum(_) => {}
+        _ => return derive_error!("WasmOption may only be derived on structs or C-like enums"),
+    };
+
+    let wasm_bindgen_meta = find_wasm_bindgen_meta(&input.attrs);
+    if wasm_bindgen_meta.is_none() {
+        return derive_error!(
+            "WasmOption can be defined only on struct exported to wasm with #[wasm_bindgen]"
+        );
+    }
+
+    let Some(option_type) = attrs.option_type else {
+        return derive_error!("WasmOption requires #[ergo(option_type = \"...\")]");
+    };
+
+    let option_type = format_ident!("{}", option_type);
+    let js_name = wasm_bindgen_meta
+        .and_then(js_class_name)
+        .unwrap_or_else(|| name.to_string());
+    let typescript_type = format!("{} | null", js_name);
+
+    let trait_name = format_ident!("__ergo__{}__TryAsOption", name);
+
+    let expanded = quote! {
+        #[wasm_bindgen]
+        extern "C" {
+            #[wasm_bindgen(typescript_type = #typescript_type)]
+            pub type #option_type;
+        }
+
+        #[allow(non_camel_case_types)]
+        pub trait #trait_name {
+            fn try_as_option(&self) -> Result<Option<#name>, ::wasm_bindgen::JsValue>;
+        }
+
+        impl #trait_name for #option_type {
+            fn try_as_option(&self) -> Result<Option<#name>, ::wasm_bindgen::JsValue> {
+                let js_value: &::wasm_bindgen::JsValue = self.as_ref();
+
+                if js_value.is_null() || js_value.is_undefined() {
+                    Ok(None)
+                } else {
+                    ::core::convert::TryFrom::try_from(js_value).map(Some)
+                }
+            }
+        }
     };
 
     TokenStream::from(expanded)